@@ -1,23 +1,64 @@
 use bevy::prelude::*;
-use bevy_rapier3d::na::{Matrix3, Vector3};
 
 use crate::actions::Actions;
+use crate::level_transition::Persistent;
 use crate::player::Player;
 use crate::GameState;
-use bevy_rapier3d::prelude::*;
 use smooth_bevy_cameras::{LookTransform, LookTransformBundle, LookTransformPlugin, Smoother};
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(setup_camera)
+        app.init_resource::<CameraConfiguration>()
+            .add_startup_system(setup_camera)
             // Enables the system that synchronizes your `Transform`s and `LookTransform`s.
             .add_plugin(LookTransformPlugin)
             .add_system_set(SystemSet::on_update(GameState::Playing).with_system(handle_camera));
     }
 }
 
+/// How the camera's eye/target are derived from the tracked yaw/pitch each frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraMode {
+    /// Orbits `distance` away from the player, always looking at them.
+    FollowOrbit,
+    /// Eye sits at the player's position, looking along the view direction.
+    FirstPerson,
+    /// Orbits like `FollowOrbit`, but keeps the current target instead of
+    /// re-locking onto the player every frame.
+    Free,
+}
+
+/// Tunables and persisted look state for [`handle_camera`].
+///
+/// Yaw/pitch are accumulated here frame-to-frame instead of being
+/// re-derived from `LookTransform::look_direction` every update, which is
+/// what let the camera flip over the poles before pitch clamping existed.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct CameraConfiguration {
+    pub mode: CameraMode,
+    /// `(min, max)` pitch in degrees; clamped every frame to prevent gimbal flip.
+    pub pitch_limit: (f32, f32),
+    pub distance: f32,
+    pub mouse_sensitivity: f32,
+    yaw: f32,
+    pitch: f32,
+}
+
+impl Default for CameraConfiguration {
+    fn default() -> Self {
+        Self {
+            mode: CameraMode::FollowOrbit,
+            pitch_limit: (-85.0, 85.0),
+            distance: 550.0,
+            mouse_sensitivity: 0.01,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+}
+
 fn setup_camera(mut commands: Commands) {
     let eye = Vec3::default();
     let target = Vec3::default();
@@ -28,6 +69,7 @@ fn setup_camera(mut commands: Commands) {
         },
         Camera3dBundle::default(),
         Name::new("Camera"),
+        Persistent,
     ));
 }
 
@@ -35,43 +77,38 @@ fn handle_camera(
     player_query: Query<&Transform, With<Player>>,
     mut camera_query: Query<&mut LookTransform>,
     actions: Res<Actions>,
+    mut config: ResMut<CameraConfiguration>,
 ) {
-    let max_distance = 550.0;
-    let mouse_sensitivity = 0.01;
     let player = match player_query.iter().next() {
         Some(transform) => transform,
         None => return,
     };
-    for mut camera in &mut camera_query {
-        camera.target = player.translation;
-        let mut direction = camera.look_direction().unwrap_or(Vect::Z);
-        if let Some(camera_movement) = actions.camera_movement {
-            // See https://en.wikipedia.org/wiki/Rotation_matrix#Basic_rotations
-            let x_angle = mouse_sensitivity * camera_movement.x;
-            let y_angle = mouse_sensitivity * camera_movement.y;
-
-            let y_axis_rotation_matrix = Matrix3::from_row_iterator(
-                #[cfg_attr(rustfmt, rustfmt::skip)]
-                [
-                    x_angle.cos(), 0., -x_angle.sin(),
-                    0., 1., 0.,
-                    x_angle.sin(), 0., x_angle.cos(),
-                ].into_iter(),
-            );
 
-            let x_axis_rotation_matrix = Matrix3::from_row_iterator(
-                #[cfg_attr(rustfmt, rustfmt::skip)]
-                [
-                    1., 0., 0.,
-                    0., y_angle.cos(), -y_angle.sin(),
-                    0., y_angle.sin(), y_angle.cos(),
-                ].into_iter(),
-            );
+    if let Some(camera_movement) = actions.camera_movement {
+        let (min_pitch, max_pitch) = config.pitch_limit;
+        config.yaw += config.mouse_sensitivity * camera_movement.x;
+        config.pitch = (config.pitch + config.mouse_sensitivity * camera_movement.y)
+            .clamp(min_pitch.to_radians(), max_pitch.to_radians());
+    }
+    let direction = Vec3::new(
+        config.pitch.cos() * config.yaw.sin(),
+        config.pitch.sin(),
+        config.pitch.cos() * config.yaw.cos(),
+    );
 
-            direction =
-                (y_axis_rotation_matrix * x_axis_rotation_matrix * Vector3::from(direction)).into();
+    for mut camera in &mut camera_query {
+        match config.mode {
+            CameraMode::FollowOrbit => {
+                camera.target = player.translation;
+                camera.eye = camera.target - direction * config.distance;
+            }
+            CameraMode::Free => {
+                camera.eye = camera.target - direction * config.distance;
+            }
+            CameraMode::FirstPerson => {
+                camera.eye = player.translation;
+                camera.target = camera.eye + direction;
+            }
         }
-
-        camera.eye = camera.target - direction * max_distance;
     }
 }
@@ -0,0 +1,157 @@
+use crate::player::Player;
+use crate::spawning::SpawnTracker;
+use crate::world_serialization::LoadRequest;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+/// Streams between scenes through trigger-zone colliders, layered on top
+/// of [`crate::world_serialization::WorldSerializationPlugin`]: entering a
+/// [`LevelTransition`] zone tears down the current level and loads the
+/// next one, while a small set of [`Persistent`] entities (player, camera)
+/// survive the swap.
+pub struct LevelTransitionPlugin;
+
+impl Plugin for LevelTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LightingConsistency>()
+            .init_resource::<PendingPlayerPlacement>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(handle_level_transitions)
+                    .with_system(place_player_at_spawn_point)
+                    .with_system(normalize_loaded_light_intensities),
+            );
+    }
+}
+
+/// Attach to a sensor collider (the trigger zone may be a compound of
+/// nested child colliders; only the zone's root entity needs this).
+#[derive(Debug, Clone, Component)]
+pub struct LevelTransition {
+    pub target_scene: String,
+    pub spawn_point: String,
+}
+
+/// Marks an entity that survives a level transition instead of being
+/// despawned with the rest of the outgoing level's `SpawnTracker` hierarchy.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct Persistent;
+
+/// Scales every point light loaded as part of a streamed-in level, so two
+/// adjacent scenes authored at different absolute brightness still read
+/// as consistently lit.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct LightingConsistency {
+    pub intensity_scale: f32,
+}
+
+impl Default for LightingConsistency {
+    fn default() -> Self {
+        Self {
+            intensity_scale: 1.0,
+        }
+    }
+}
+
+/// Name of the entity the player should be warped onto once the scene
+/// requested by [`LoadRequest::filename`] finishes loading. The named
+/// entity may not exist yet the same frame the load is requested, so this
+/// is retried every frame until it resolves.
+#[derive(Resource, Default)]
+struct PendingPlayerPlacement(Option<String>);
+
+fn handle_level_transitions(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<Entity, With<Player>>,
+    parents: Query<&Parent>,
+    transitions: Query<&LevelTransition>,
+    mut load_requests: EventWriter<LoadRequest>,
+    mut pending_placement: ResMut<PendingPlayerPlacement>,
+    despawn_candidates: Query<Entity, (With<SpawnTracker>, Without<Persistent>, Without<Player>)>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for event in collision_events.iter() {
+        let CollisionEvent::Started(a, b, flags) = event else {
+            continue;
+        };
+        if !flags.contains(CollisionEventFlags::SENSOR) {
+            continue;
+        }
+        let Some(other) = other_collider(*a, *b, player_entity) else {
+            continue;
+        };
+        let Some(transition) = find_level_transition(other, &transitions, &parents) else {
+            continue;
+        };
+
+        for entity in &despawn_candidates {
+            commands.entity(entity).despawn_recursive();
+        }
+        pending_placement.0 = Some(transition.spawn_point.clone());
+        load_requests.send(LoadRequest {
+            filename: transition.target_scene.clone(),
+        });
+    }
+}
+
+fn other_collider(a: Entity, b: Entity, player: Entity) -> Option<Entity> {
+    if a == player {
+        Some(b)
+    } else if b == player {
+        Some(a)
+    } else {
+        None
+    }
+}
+
+/// Resolves a [`LevelTransition`] from `collider`'s ancestor chain, since a
+/// compound trigger zone's directly-hit collider is usually a child of the
+/// entity the transition is actually declared on.
+fn find_level_transition(
+    collider: Entity,
+    transitions: &Query<&LevelTransition>,
+    parents: &Query<&Parent>,
+) -> Option<LevelTransition> {
+    let mut current = collider;
+    loop {
+        if let Ok(transition) = transitions.get(current) {
+            return Some(transition.clone());
+        }
+        current = parents.get(current).ok()?.get();
+    }
+}
+
+fn place_player_at_spawn_point(
+    mut pending_placement: ResMut<PendingPlayerPlacement>,
+    mut player_query: Query<&mut Transform, With<Player>>,
+    named_entities: Query<(&Name, &Transform), Without<Player>>,
+) {
+    let Some(spawn_point) = &pending_placement.0 else {
+        return;
+    };
+    let Some((_, target_transform)) = named_entities
+        .iter()
+        .find(|(name, _)| name.as_str() == spawn_point.as_str())
+    else {
+        return; // scene hasn't finished streaming in yet; retry next frame
+    };
+    let target_transform = *target_transform;
+    if let Ok(mut player_transform) = player_query.get_single_mut() {
+        *player_transform = target_transform;
+    }
+    pending_placement.0 = None;
+}
+
+fn normalize_loaded_light_intensities(
+    lighting: Res<LightingConsistency>,
+    mut lights: Query<&mut PointLight, Added<PointLight>>,
+) {
+    for mut light in &mut lights {
+        light.intensity *= lighting.intensity_scale;
+    }
+}
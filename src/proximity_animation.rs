@@ -0,0 +1,108 @@
+use crate::player::Player;
+use crate::GameState;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Cross-fades a spawned entity's glTF animation between clips picked by
+/// its distance to the [`Player`] (e.g. an idle clip up close, a beckoning
+/// clip from afar). Clip handles are ordinary `Handle<AnimationClip>`s, so
+/// they can point at the same glTF assets used by
+/// [`crate::spawning::blueprints::BlueprintPlugin`].
+pub struct ProximityAnimationPlugin;
+
+impl Plugin for ProximityAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(GameState::Playing).with_system(drive_proximity_animations),
+        );
+    }
+}
+
+const CROSS_FADE: Duration = Duration::from_millis(250);
+
+/// Distance bands, sorted ascending by `distance_threshold`. The active
+/// clip is the one belonging to the furthest threshold the entity's
+/// distance to the player has reached or passed; `clips[0]`'s threshold is
+/// effectively the "close up" fallback and is typically `0.0`.
+#[derive(Debug, Clone, Component)]
+pub struct AnimatedBy {
+    pub clips: Vec<(f32, Handle<AnimationClip>)>,
+}
+
+/// Tracks which band of [`AnimatedBy::clips`] is currently playing, so
+/// [`drive_proximity_animations`] only starts a cross-fade when the band
+/// actually changes instead of restarting the clip every frame.
+#[derive(Debug, Clone, Copy, Component)]
+struct CurrentAnimationBand(usize);
+
+fn drive_proximity_animations(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    animated_query: Query<(
+        Entity,
+        &GlobalTransform,
+        &AnimatedBy,
+        Option<&CurrentAnimationBand>,
+    )>,
+    children_query: Query<&Children>,
+    mut animation_players: Query<&mut AnimationPlayer>,
+) {
+    let Some(player_transform) = player_query.iter().next() else {
+        return;
+    };
+
+    for (entity, transform, animated_by, current_band) in &animated_query {
+        if animated_by.clips.is_empty() {
+            continue;
+        }
+        let distance = transform.translation().distance(player_transform.translation);
+        let band = band_for_distance(&animated_by.clips, distance);
+        if current_band.is_some_and(|current| current.0 == band) {
+            continue;
+        }
+        let Some(player_entity) = find_animation_player(entity, &children_query, &animation_players) else {
+            continue;
+        };
+        let mut player = animation_players.get_mut(player_entity).expect(
+            "find_animation_player only returns entities that have an AnimationPlayer",
+        );
+        player
+            .play_with_transition(animated_by.clips[band].1.clone(), CROSS_FADE)
+            .repeat();
+        commands.entity(entity).insert(CurrentAnimationBand(band));
+    }
+}
+
+/// Finds the `AnimationPlayer` actually driving `root`'s glTF animations.
+/// Bevy's glTF loader attaches the player to whichever node is the
+/// animation target, which for a blueprint-merged scene is typically a
+/// child (e.g. an `Armature`) rather than `root` itself, so this walks the
+/// hierarchy breadth-first looking for the first descendant that has one.
+fn find_animation_player(
+    root: Entity,
+    children_query: &Query<&Children>,
+    animation_players: &Query<&mut AnimationPlayer>,
+) -> Option<Entity> {
+    let mut queue = vec![root];
+    while let Some(entity) = queue.pop() {
+        if animation_players.contains(entity) {
+            return Some(entity);
+        }
+        if let Ok(children) = children_query.get(entity) {
+            queue.extend(children.iter().copied());
+        }
+    }
+    None
+}
+
+/// Index into `clips` of the band whose threshold the given `distance` has
+/// reached or passed, i.e. the largest threshold that's `<= distance`.
+fn band_for_distance(clips: &[(f32, Handle<AnimationClip>)], distance: f32) -> usize {
+    clips
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, (threshold, _))| distance >= *threshold)
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
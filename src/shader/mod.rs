@@ -0,0 +1,249 @@
+mod preprocessor;
+
+use crate::GameState;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef, ShaderType};
+use preprocessor::ShaderPreprocessor;
+use std::path::Path;
+
+pub struct ShaderPlugin;
+
+impl Plugin for ShaderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(MaterialPlugin::<GlowyMaterial>::default())
+            .init_resource::<ShadowSettings>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Loading)
+                    .with_system(preprocess_shaders.before("setup_shader"))
+                    .with_system(setup_shader.label("setup_shader")),
+            )
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_shader))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(apply_shader))
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing).with_system(sync_shadow_config),
+            );
+    }
+}
+
+/// Declares where a material's entry shader lives and which feature flags
+/// (`#ifdef`s) should be active when it's flattened, so the preprocessor
+/// knows what to build without the material having to run it itself.
+struct MaterialShaderSource {
+    entry: &'static str,
+    features: &'static [&'static str],
+    /// Fixed output path materials' `fragment_shader()` points at; kept
+    /// stable across runs so `Material::fragment_shader` can return a
+    /// plain string instead of a dynamically chosen handle.
+    generated: &'static str,
+}
+
+const GLOWY_SHADER_SOURCE: MaterialShaderSource = MaterialShaderSource {
+    entry: "glowy.wgsl",
+    features: &["SHADOWS"],
+    generated: "glowy.generated.wgsl",
+};
+
+/// Runs [`ShaderPreprocessor`] over every material's declared shader source
+/// and writes the flattened result next to it, before any material is
+/// loaded by Bevy's own asset pipeline.
+fn preprocess_shaders() {
+    let shaders_dir = Path::new("assets").join("shaders");
+    let preprocessor = ShaderPreprocessor::new(&shaders_dir);
+    for source in [&GLOWY_SHADER_SOURCE] {
+        match preprocessor.process(source.entry, source.features) {
+            Ok(flattened) => {
+                let out_path = shaders_dir.join(source.generated);
+                if let Err(e) = std::fs::write(&out_path, flattened) {
+                    error!(
+                        "Failed to write preprocessed shader \"{}\": {}",
+                        out_path.to_string_lossy(),
+                        e
+                    );
+                }
+            }
+            Err(e) => error!("Failed to preprocess shader \"{}\": {:?}", source.entry, e),
+        }
+    }
+}
+
+/// Global fallback used for lights that don't carry their own [`ShadowConfig`].
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ShadowSettings {
+    pub quality: ShadowQuality,
+    pub default_depth_bias: f32,
+    pub default_light_size: f32,
+}
+
+impl Default for ShadowSettings {
+    fn default() -> Self {
+        Self {
+            quality: ShadowQuality::MultiTapPcf,
+            default_depth_bias: 0.02,
+            default_light_size: 0.5,
+        }
+    }
+}
+
+/// Shadow filtering mode, from cheapest to most expensive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowQuality {
+    /// No shadow map at all for the affected light.
+    Disabled,
+    /// Bevy's built-in hardware 2x2 PCF.
+    HardwarePcf,
+    /// 16-tap rotated-Poisson-disc PCF, sampled in `glowy.wgsl`.
+    MultiTapPcf,
+    /// Multi-tap PCF with a blocker-search pass that scales the filter
+    /// radius by the estimated penumbra width.
+    Pcss,
+}
+
+/// Per-light override for [`ShadowSettings`]; attach this to a light entity
+/// to tune acne (`depth_bias`) vs. peter-panning independently of the
+/// global default, and to set the light's apparent size for PCSS.
+///
+/// `glowy.wgsl` only ever samples `lights.point_lights[0]`, and
+/// [`sync_shadow_config`] writes whichever light last changed onto every
+/// [`GlowyMaterial`] instance, so in practice this only tunes shadows
+/// correctly for a scene with a single point light and a single glowy
+/// material; a second light or material instance will fight over the same
+/// shared uniform. Revisit both before using more than one of either.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShadowConfig {
+    pub quality: Option<ShadowQuality>,
+    pub depth_bias: f32,
+    pub light_size: f32,
+}
+
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            quality: None,
+            depth_bias: 0.02,
+            light_size: 0.5,
+        }
+    }
+}
+
+/// Applies [`ShadowConfig`] (falling back to [`ShadowSettings`]) onto
+/// Bevy's native [`PointLight`] shadow fields, and onto the [`GlowyMaterial`]
+/// instances used to render glow, so the two stay in sync.
+///
+/// Writes every [`GlowyMaterial`] instance unconditionally for whichever
+/// light triggered this system, last write wins across lights. That's a
+/// single-global-light assumption, matching `glowy.wgsl` hardcoding
+/// `lights.point_lights[0]`; see [`ShadowConfig`]'s doc comment.
+fn sync_shadow_config(
+    settings: Res<ShadowSettings>,
+    mut lights: Query<(&mut PointLight, Option<&ShadowConfig>), Changed<PointLight>>,
+    mut glow_materials: ResMut<Assets<GlowyMaterial>>,
+) {
+    for (mut point_light, config) in &mut lights {
+        let quality = config
+            .and_then(|c| c.quality)
+            .unwrap_or(settings.quality);
+        let depth_bias = config.map(|c| c.depth_bias).unwrap_or(settings.default_depth_bias);
+        let light_size = config.map(|c| c.light_size).unwrap_or(settings.default_light_size);
+
+        point_light.shadows_enabled = quality != ShadowQuality::Disabled;
+        point_light.shadow_depth_bias = depth_bias;
+
+        for (_, material) in glow_materials.iter_mut() {
+            material.shadow.quality = quality as u32;
+            material.shadow.depth_bias = depth_bias;
+            material.shadow.light_size = light_size;
+        }
+    }
+}
+
+fn setup_shader(
+    mut commands: Commands,
+    mut glow_materials: ResMut<Assets<GlowyMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    let env_texture_path = Path::new("hdri").join("stone_alley_2.hdr");
+    let env_texture = asset_server.load(env_texture_path);
+    let material = glow_materials.add(GlowyMaterial {
+        env_texture: Some(env_texture),
+        shadow: ShadowUniform {
+            quality: ShadowQuality::MultiTapPcf as u32,
+            depth_bias: 0.02,
+            light_size: 0.5,
+        },
+    });
+    commands.insert_resource(Materials { glowy: material });
+}
+#[derive(Resource, Debug, Clone)]
+struct Materials {
+    pub glowy: Handle<GlowyMaterial>,
+}
+
+fn spawn_shader(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    materials: Res<Materials>,
+) {
+    commands
+        .spawn(MaterialMeshBundle {
+            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                radius: 1.0,
+                ..default()
+            })),
+            material: materials.glowy.clone(),
+            transform: Transform::from_translation((0., 1.5, 0.).into()),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                PointLightBundle {
+                    point_light: PointLight {
+                        intensity: 10_000.,
+                        radius: 1.,
+                        color: Color::rgb(0.5, 0.1, 0.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ShadowConfig::default(),
+            ));
+        });
+}
+
+#[derive(AsBindGroup, Debug, Clone, TypeUuid)]
+#[uuid = "bd5c76fd-6fdd-4de4-9744-4e8beea8daaf"]
+pub struct GlowyMaterial {
+    #[texture(0)]
+    #[sampler(1)]
+    pub env_texture: Option<Handle<Image>>,
+    #[uniform(2)]
+    pub shadow: ShadowUniform,
+}
+
+/// Per-fragment shadow parameters handed to `glowy.wgsl`, kept in sync with
+/// [`ShadowConfig`]/[`ShadowSettings`] by [`sync_shadow_config`].
+#[derive(Debug, Clone, Copy, ShaderType)]
+pub struct ShadowUniform {
+    pub depth_bias: f32,
+    pub light_size: f32,
+    pub quality: u32,
+}
+
+impl Material for GlowyMaterial {
+    fn fragment_shader() -> ShaderRef {
+        format!("shaders/{}", GLOWY_SHADER_SOURCE.generated).into()
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_shader(
+    mut commands: Commands,
+    added_name: Query<(Entity, &Name), Added<Name>>,
+    materials: Res<Materials>,
+) {
+    for (entity, name) in &added_name {
+        if name.to_lowercase().contains("plane") {
+            commands.entity(entity).insert(materials.glowy.clone());
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Runs `#include`/`#define`/`#ifdef` over a material's WGSL source before
+/// it reaches Bevy, so shared lighting/shadow/tonemapping code can live in
+/// one file instead of being copy-pasted into every material.
+///
+/// Paths in `#include "path.wgsl"` are resolved relative to `shaders_dir`
+/// (normally the `shaders/` asset directory), recursively, with cycle
+/// detection. Each file is only ever included once per [`ShaderPreprocessor::process`]
+/// call, mirroring a C-style `#pragma once`.
+pub struct ShaderPreprocessor {
+    shaders_dir: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum PreprocessError {
+    Io(PathBuf, io::Error),
+    IncludeCycle(Vec<PathBuf>),
+    UnterminatedIfdef(PathBuf),
+    DanglingElse(PathBuf),
+    DanglingEndif(PathBuf),
+}
+
+impl ShaderPreprocessor {
+    pub fn new(shaders_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            shaders_dir: shaders_dir.into(),
+        }
+    }
+
+    /// Flattens `entry` (a path relative to `shaders_dir`) into a single
+    /// WGSL source string, with `defines` active for `#ifdef`/`#define`
+    /// substitution.
+    pub fn process(
+        &self,
+        entry: impl AsRef<Path>,
+        defines: &[&str],
+    ) -> Result<String, PreprocessError> {
+        let mut defines: HashMap<String, String> =
+            defines.iter().map(|name| (name.to_string(), String::new())).collect();
+        let mut once_guard = HashSet::new();
+        let mut stack = Vec::new();
+        self.process_file(entry.as_ref(), &mut defines, &mut once_guard, &mut stack)
+    }
+
+    fn process_file(
+        &self,
+        relative_path: &Path,
+        defines: &mut HashMap<String, String>,
+        once_guard: &mut HashSet<PathBuf>,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<String, PreprocessError> {
+        let absolute_path = self.shaders_dir.join(relative_path);
+        if stack.contains(&absolute_path) {
+            let mut cycle = stack.clone();
+            cycle.push(absolute_path);
+            return Err(PreprocessError::IncludeCycle(cycle));
+        }
+        if !once_guard.insert(absolute_path.clone()) {
+            // Already flattened earlier in this compilation; skip silently,
+            // same as a C `#pragma once` header guard.
+            return Ok(String::new());
+        }
+
+        let source = fs::read_to_string(&absolute_path)
+            .map_err(|e| PreprocessError::Io(absolute_path.clone(), e))?;
+        stack.push(absolute_path.clone());
+        let flattened = self.process_source(&source, &absolute_path, defines, once_guard, stack)?;
+        stack.pop();
+        Ok(flattened)
+    }
+
+    fn process_source(
+        &self,
+        source: &str,
+        current_file: &Path,
+        defines: &mut HashMap<String, String>,
+        once_guard: &mut HashSet<PathBuf>,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<String, PreprocessError> {
+        // Active branch state for nested #ifdef/#else/#endif: whether the
+        // current branch is emitting, and whether its condition was true
+        // (so `#else` knows to flip).
+        let mut branch_stack: Vec<bool> = Vec::new();
+        let mut out = String::new();
+
+        for line in source.lines() {
+            let trimmed = line.trim_start();
+            let emitting = branch_stack.iter().all(|&active| active);
+
+            if let Some(rest) = trimmed.strip_prefix("#include ") {
+                if !emitting {
+                    continue;
+                }
+                let included = parse_quoted(rest);
+                out.push_str(&self.process_file(Path::new(included), defines, once_guard, stack)?);
+                out.push('\n');
+            } else if let Some(rest) = trimmed.strip_prefix("#define ") {
+                if !emitting {
+                    continue;
+                }
+                let (name, value) = rest.split_once(' ').unwrap_or((rest.trim(), ""));
+                defines.insert(name.trim().to_string(), value.trim().to_string());
+            } else if let Some(name) = trimmed.strip_prefix("#ifdef ") {
+                branch_stack.push(defines.contains_key(name.trim()));
+            } else if trimmed.starts_with("#else") {
+                match branch_stack.last_mut() {
+                    Some(active) => *active = !*active,
+                    None => return Err(PreprocessError::DanglingElse(current_file.to_path_buf())),
+                }
+            } else if trimmed.starts_with("#endif") {
+                if branch_stack.pop().is_none() {
+                    return Err(PreprocessError::DanglingEndif(current_file.to_path_buf()));
+                }
+            } else if emitting {
+                out.push_str(&substitute_defines(line, defines));
+                out.push('\n');
+            }
+        }
+
+        if !branch_stack.is_empty() {
+            return Err(PreprocessError::UnterminatedIfdef(current_file.to_path_buf()));
+        }
+        Ok(out)
+    }
+}
+
+fn parse_quoted(s: &str) -> &str {
+    s.trim().trim_matches('"')
+}
+
+fn substitute_defines(line: &str, defines: &HashMap<String, String>) -> String {
+    let mut result = line.to_string();
+    for (name, value) in defines {
+        if value.is_empty() {
+            continue;
+        }
+        result = replace_identifier(&result, name, value);
+    }
+    result
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Like `str::replace`, but only replaces occurrences of `name` that aren't
+/// part of a larger identifier, so e.g. a `#define E 5` doesn't corrupt
+/// `ENV_SCALE` into `5NV_SCALE`.
+fn replace_identifier(haystack: &str, name: &str, value: &str) -> String {
+    let mut out = String::with_capacity(haystack.len());
+    let mut rest = haystack;
+    while let Some(offset) = rest.find(name) {
+        let (before, after_match) = rest.split_at(offset);
+        let after = &after_match[name.len()..];
+        let before_ok = !before.chars().next_back().is_some_and(is_identifier_char);
+        let after_ok = !after.chars().next().is_some_and(is_identifier_char);
+
+        out.push_str(before);
+        if before_ok && after_ok {
+            out.push_str(value);
+        } else {
+            out.push_str(name);
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, contents: &str) {
+        fs::write(dir.join(name), contents).unwrap();
+    }
+
+    #[test]
+    fn includes_are_flattened_and_deduplicated() {
+        let dir = std::env::temp_dir().join(format!("foxtrot-shader-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        write(&dir, "lib.wgsl", "fn lib() -> f32 { return 1.0; }");
+        write(
+            &dir,
+            "entry.wgsl",
+            "#include \"lib.wgsl\"\n#include \"lib.wgsl\"\nfn main() {}",
+        );
+
+        let processed = ShaderPreprocessor::new(&dir)
+            .process("entry.wgsl", &[])
+            .unwrap();
+
+        assert_eq!(processed.matches("fn lib()").count(), 1);
+        assert!(processed.contains("fn main()"));
+    }
+
+    #[test]
+    fn ifdef_gates_on_active_defines() {
+        let dir = std::env::temp_dir().join(format!("foxtrot-shader-test-ifdef-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        write(
+            &dir,
+            "entry.wgsl",
+            "#ifdef SHADOWS\nfn shadows() {}\n#else\nfn no_shadows() {}\n#endif",
+        );
+
+        let with_shadows = ShaderPreprocessor::new(&dir)
+            .process("entry.wgsl", &["SHADOWS"])
+            .unwrap();
+        assert!(with_shadows.contains("fn shadows()"));
+        assert!(!with_shadows.contains("fn no_shadows()"));
+
+        let without_shadows = ShaderPreprocessor::new(&dir).process("entry.wgsl", &[]).unwrap();
+        assert!(!without_shadows.contains("fn shadows()"));
+        assert!(without_shadows.contains("fn no_shadows()"));
+    }
+
+    #[test]
+    fn define_substitution_respects_identifier_boundaries() {
+        let dir = std::env::temp_dir().join(format!("foxtrot-shader-test-define-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        write(
+            &dir,
+            "entry.wgsl",
+            "#define E 5\nlet x = E;\nlet env_scale = ENV_SCALE;",
+        );
+
+        let processed = ShaderPreprocessor::new(&dir)
+            .process("entry.wgsl", &[])
+            .unwrap();
+
+        assert!(processed.contains("let x = 5;"));
+        assert!(processed.contains("let env_scale = ENV_SCALE;"));
+    }
+
+    #[test]
+    fn detects_include_cycles() {
+        let dir = std::env::temp_dir().join(format!("foxtrot-shader-test-cycle-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        write(&dir, "a.wgsl", "#include \"b.wgsl\"");
+        write(&dir, "b.wgsl", "#include \"a.wgsl\"");
+
+        let result = ShaderPreprocessor::new(&dir).process("a.wgsl", &[]);
+        assert!(matches!(result, Err(PreprocessError::IncludeCycle(_))));
+    }
+}
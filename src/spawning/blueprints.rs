@@ -0,0 +1,200 @@
+use bevy::ecs::system::Command;
+use bevy::prelude::*;
+use bevy::reflect::TypeRegistry;
+use bevy::scene::Scene;
+use std::path::PathBuf;
+
+/// Replaces hardcoded [`crate::spawning::GameObject`] variants with
+/// glTF-authored content: any `.glb`/`.gltf` dropped into
+/// [`BlueprintsConfig::library_folder`] becomes spawnable by name, without
+/// the engine needing to know about it at compile time.
+pub struct BlueprintPlugin;
+
+impl Plugin for BlueprintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BlueprintsConfig>()
+            .add_event::<AfterSpawn>()
+            .add_system(request_blueprint_scenes)
+            .add_system(merge_blueprint_scenes);
+    }
+}
+
+/// Folder (relative to `assets/`) holding the blueprint library.
+#[derive(Resource, Debug, Clone)]
+pub struct BlueprintsConfig {
+    pub library_folder: PathBuf,
+}
+
+impl Default for BlueprintsConfig {
+    fn default() -> Self {
+        Self {
+            library_folder: PathBuf::from("blueprints"),
+        }
+    }
+}
+
+/// Name (without extension) of the glTF blueprint to instantiate onto the
+/// entity carrying this component.
+#[derive(Debug, Clone, Component)]
+pub struct BlueprintName(pub String);
+
+/// Marks an entity whose [`BlueprintName`] still needs to be loaded and
+/// merged onto it. Removed once [`merge_blueprint_scenes`] finishes.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct SpawnHere;
+
+#[derive(Bundle)]
+pub struct BluePrintBundle {
+    pub blueprint: BlueprintName,
+    pub spawn_here: SpawnHere,
+    pub transform: Transform,
+}
+
+/// Fired once a blueprint's root components and children have been copied
+/// onto `entity`, so gameplay/UI systems can react to the fully-formed entity.
+pub struct AfterSpawn {
+    pub entity: Entity,
+}
+
+#[derive(Component)]
+struct LoadingBlueprintScene(Handle<Scene>);
+
+fn request_blueprint_scenes(
+    mut commands: Commands,
+    config: Res<BlueprintsConfig>,
+    asset_server: Res<AssetServer>,
+    to_spawn: Query<(Entity, &BlueprintName), (With<SpawnHere>, Without<LoadingBlueprintScene>)>,
+) {
+    for (entity, blueprint) in &to_spawn {
+        let path = config
+            .library_folder
+            .join(format!("{}.glb#Scene0", blueprint.0));
+        let scene: Handle<Scene> = asset_server.load(path);
+        commands.entity(entity).insert(LoadingBlueprintScene(scene));
+    }
+}
+
+fn merge_blueprint_scenes(
+    mut commands: Commands,
+    scenes: Res<Assets<Scene>>,
+    loading: Query<(Entity, &LoadingBlueprintScene), With<SpawnHere>>,
+) {
+    for (entity, loading) in &loading {
+        if scenes.get(&loading.0).is_none() {
+            continue; // still loading
+        }
+        commands.add(MergeBlueprintScene {
+            target: entity,
+            scene: loading.0.clone(),
+        });
+    }
+}
+
+struct MergeBlueprintScene {
+    target: Entity,
+    scene: Handle<Scene>,
+}
+
+impl Command for MergeBlueprintScene {
+    fn write(self, world: &mut World) {
+        let Some(scene) = world.resource::<Assets<Scene>>().get(&self.scene) else {
+            return;
+        };
+        let type_registry = world.resource::<AppTypeRegistry>().0.clone();
+        let type_registry = type_registry.read();
+        let source_world = &scene.world;
+
+        let roots: Vec<Entity> = source_world
+            .iter_entities()
+            .map(|entity_ref| entity_ref.id())
+            .filter(|&source_entity| source_world.get::<Parent>(source_entity).is_none())
+            .collect();
+
+        for source_root in roots {
+            copy_components_onto(source_world, world, source_root, self.target, &type_registry);
+            spawn_children(source_world, world, source_root, self.target, &type_registry);
+        }
+
+        world.entity_mut(self.target).remove::<LoadingBlueprintScene>();
+        world.entity_mut(self.target).remove::<SpawnHere>();
+        world
+            .resource_mut::<Events<AfterSpawn>>()
+            .send(AfterSpawn {
+                entity: self.target,
+            });
+    }
+}
+
+/// Reflect-copies every component on `source_entity` onto
+/// `destination_entity` that `destination_entity` doesn't already own.
+/// `Parent`/`Children` are skipped; hierarchy is rebuilt explicitly by
+/// [`spawn_children`] since source-world entity IDs are meaningless in
+/// `destination_world`.
+fn copy_components_onto(
+    source_world: &World,
+    destination_world: &mut World,
+    source_entity: Entity,
+    destination_entity: Entity,
+    type_registry: &TypeRegistry,
+) {
+    let source_entity_ref = source_world.entity(source_entity);
+    for component_id in source_entity_ref.archetype().components() {
+        let Some(type_id) = source_world
+            .components()
+            .get_info(component_id)
+            .and_then(|info| info.type_id())
+        else {
+            continue;
+        };
+        if type_id == std::any::TypeId::of::<Parent>()
+            || type_id == std::any::TypeId::of::<Children>()
+        {
+            continue;
+        }
+        let Some(reflect_component) = type_registry
+            .get(type_id)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            continue;
+        };
+        if destination_world
+            .entity(destination_entity)
+            .contains_type_id(type_id)
+        {
+            continue;
+        }
+        reflect_component.copy(source_world, destination_world, source_entity, destination_entity);
+    }
+}
+
+fn spawn_children(
+    source_world: &World,
+    destination_world: &mut World,
+    source_parent: Entity,
+    destination_parent: Entity,
+    type_registry: &TypeRegistry,
+) {
+    let Some(children) = source_world.get::<Children>(source_parent) else {
+        return;
+    };
+    for &source_child in children.iter() {
+        let destination_child = destination_world.spawn(()).id();
+        copy_components_onto(
+            source_world,
+            destination_world,
+            source_child,
+            destination_child,
+            type_registry,
+        );
+        destination_world
+            .entity_mut(destination_child)
+            .set_parent(destination_parent);
+        spawn_children(
+            source_world,
+            destination_world,
+            source_child,
+            destination_child,
+            type_registry,
+        );
+    }
+}
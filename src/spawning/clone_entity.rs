@@ -0,0 +1,49 @@
+use bevy::ecs::system::Command;
+use bevy::prelude::*;
+use std::any::TypeId;
+
+/// Reflect-copies every component on `source` onto `destination`,
+/// skipping any component `destination` already owns. Generalizes the
+/// per-variant rebuilding that duplicating a [`crate::spawning::GameObject`]
+/// used to require: any component registered for reflection is carried
+/// over automatically, without the caller needing to know what it is.
+///
+/// Both `source` and `destination` must already exist, or this command
+/// panics. Components not registered in the [`AppTypeRegistry`] are
+/// silently skipped, since there's no way to reflect-clone them.
+pub struct CloneEntity {
+    pub source: Entity,
+    pub destination: Entity,
+}
+
+impl Command for CloneEntity {
+    fn write(self, world: &mut World) {
+        let type_registry = world.resource::<AppTypeRegistry>().0.clone();
+        let type_registry = type_registry.read();
+
+        let component_ids: Vec<_> = world.entity(self.source).archetype().components().collect();
+        let cloned: Vec<(TypeId, Box<dyn Reflect>)> = component_ids
+            .into_iter()
+            .filter_map(|component_id| {
+                let type_id = world.components().get_info(component_id)?.type_id()?;
+                let reflect_component = type_registry.get(type_id)?.data::<ReflectComponent>()?;
+                let value = reflect_component.reflect(world.entity(self.source))?;
+                Some((type_id, value.clone_value()))
+            })
+            .collect();
+
+        for (type_id, value) in cloned {
+            if world
+                .entity(self.destination)
+                .contains_type_id(type_id)
+            {
+                continue;
+            }
+            let reflect_component = type_registry
+                .get(type_id)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+                .expect("type was reflected off `source` above, so it's registered");
+            reflect_component.insert(world, self.destination, &*value);
+        }
+    }
+}
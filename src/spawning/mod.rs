@@ -0,0 +1,166 @@
+mod blueprints;
+mod clone_entity;
+
+pub use blueprints::{BluePrintBundle, BlueprintName, BlueprintsConfig, SpawnHere};
+pub use clone_entity::CloneEntity;
+
+use crate::GameState;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub struct SpawningPlugin;
+
+impl Plugin for SpawningPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpawnTracker>()
+            .add_plugin(blueprints::BlueprintPlugin)
+            .add_event::<SpawnEvent>()
+            .add_event::<DuplicateEntity>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(spawn_requested.label("spawn_requested"))
+                    .with_system(duplicate_requested.label("spawn_requested")),
+            );
+    }
+}
+
+/// Things the engine knows how to spawn: either a compile-time known
+/// variant, or the name of a blueprint glTF found in
+/// [`BlueprintsConfig::library_folder`]. Scenes can reference any
+/// authored glTF asset by name without engine code changes by using the
+/// latter.
+#[derive(Debug, Clone, Eq, PartialEq, Reflect, Serialize, Deserialize, Default)]
+pub enum GameObject {
+    #[default]
+    Player,
+    Car,
+    Tree,
+    Sunlight,
+    Blueprint(String),
+}
+
+impl GameObject {
+    pub fn get_default_name(&self) -> &str {
+        match self {
+            GameObject::Player => "Player",
+            GameObject::Car => "Car",
+            GameObject::Tree => "Tree",
+            GameObject::Sunlight => "Sunlight",
+            GameObject::Blueprint(name) => name,
+        }
+    }
+}
+
+/// Marks an entity as having been created from a [`SpawnEvent`], and
+/// records which [`GameObject`] it was spawned from so the world can be
+/// re-serialized later. Reflected (rather than handled as one of
+/// [`crate::world_serialization`]'s dedicated `SavedEntity` fields) so
+/// that [`CloneEntity`] can carry it over without needing to special-case
+/// `GameObject`'s variants.
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct SpawnTracker {
+    pub object: GameObject,
+}
+
+impl SpawnTracker {
+    pub fn get_default_name(&self) -> &str {
+        self.object.get_default_name()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Serialize, Deserialize)]
+pub struct SpawnEvent {
+    pub object: GameObject,
+    pub transform: Transform,
+    pub name: Option<String>,
+    pub parent: Option<String>,
+}
+
+fn spawn_requested(
+    mut commands: Commands,
+    mut spawn_requests: EventReader<SpawnEvent>,
+    named_entities: Query<(Entity, &Name)>,
+) {
+    for event in spawn_requests.iter() {
+        let name = event
+            .name
+            .clone()
+            .unwrap_or_else(|| event.object.get_default_name().to_string());
+        let mut entity_commands = commands.spawn((
+            SpawnTracker {
+                object: event.object.clone(),
+            },
+            GlobalTransform::default(),
+            Name::new(name),
+        ));
+
+        if let GameObject::Blueprint(blueprint_name) = &event.object {
+            entity_commands.insert(BluePrintBundle {
+                blueprint: BlueprintName(blueprint_name.clone()),
+                spawn_here: SpawnHere,
+                transform: event.transform,
+            });
+        } else {
+            entity_commands.insert(event.transform);
+        }
+
+        if let Some(parent_name) = &event.parent {
+            if let Some((parent_entity, _)) = named_entities
+                .iter()
+                .find(|(_, name)| name.as_str() == parent_name)
+            {
+                entity_commands.set_parent(parent_entity);
+            }
+        }
+    }
+}
+
+/// Requests a reflect-complete copy of `source`, e.g. for a "spawn another
+/// of this" editor action. Unlike [`SpawnEvent`], the caller doesn't need
+/// to know `source`'s [`GameObject`] variant or any of its other
+/// components; [`duplicate_requested`] carries all of it over via
+/// [`CloneEntity`].
+#[derive(Debug, Clone, Copy)]
+pub struct DuplicateEntity {
+    pub source: Entity,
+}
+
+fn duplicate_requested(
+    mut commands: Commands,
+    mut duplicate_requests: EventReader<DuplicateEntity>,
+    named_entities: Query<&Name>,
+) {
+    // Seeded from the current world and then updated as names are chosen
+    // below, since `Name` inserts are deferred commands that haven't
+    // applied yet: without this, two `DuplicateEntity`s for the same
+    // `source` in one frame would both see the same stale snapshot and
+    // collide on the same suffix.
+    let mut claimed_names: HashSet<String> =
+        named_entities.iter().map(|name| name.to_string()).collect();
+
+    for request in duplicate_requests.iter() {
+        let destination = commands.spawn(GlobalTransform::default()).id();
+        commands.add(CloneEntity {
+            source: request.source,
+            destination,
+        });
+        if let Ok(source_name) = named_entities.get(request.source) {
+            let unique_name = unique_name(source_name.as_str(), &claimed_names);
+            claimed_names.insert(unique_name.clone());
+            commands.entity(destination).insert(Name::new(unique_name));
+        }
+    }
+}
+
+/// Finds the first `"{base}-{n}"` (n starting at 1) not already taken in
+/// `claimed_names`, mirroring the suffix probing
+/// [`crate::world_serialization`]'s `save_world` uses for save file names.
+fn unique_name(base: &str, claimed_names: &HashSet<String>) -> String {
+    (1..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !claimed_names.contains(candidate))
+        .expect("infinite suffix range always yields an unused name")
+}
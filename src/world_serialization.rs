@@ -1,7 +1,15 @@
-use crate::spawning::{SpawnEvent, SpawnTracker};
+use crate::level_transition::Persistent;
+use crate::player::Player;
+use crate::spawning::{GameObject, SpawnEvent, SpawnTracker};
+use bevy::ecs::reflect::ReflectResource;
 use bevy::prelude::*;
+use bevy::reflect::serde::{ReflectSerializer, UntypedReflectDeserializer};
+use bevy::reflect::TypeRegistry;
+use serde::de::DeserializeSeed;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::{fs, iter};
 
 pub struct WorldSerializationPlugin;
@@ -10,8 +18,12 @@ impl Plugin for WorldSerializationPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SaveRequest>()
             .add_event::<LoadRequest>()
+            .add_event::<SaveFinished>()
+            .init_resource::<SaveConfig>()
+            .init_resource::<PendingRehydration>()
             .add_system(save_world.after("spawn_requested"))
-            .add_system(load_world.after("spawn_requested"));
+            .add_system(load_world.after("spawn_requested"))
+            .add_system(rehydrate_spawned_entities.after("spawn_requested"));
     }
 }
 
@@ -27,14 +39,109 @@ pub struct LoadRequest {
     pub filename: String,
 }
 
-fn save_world(
-    mut save_requests: EventReader<SaveRequest>,
-    spawn_query: Query<(&SpawnTracker, &Name, Option<&Parent>, Option<&Transform>)>,
-) {
-    for save in save_requests.iter() {
+/// Fired once [`save_world`] has finished writing `path`, so UI/gameplay
+/// systems can react (e.g. show a "Saved!" toast) without polling the
+/// filesystem.
+pub struct SaveFinished {
+    pub path: PathBuf,
+}
+
+/// Which reflected types [`save_world`] is allowed to write out. Applies
+/// separately to components (via [`SaveConfig::components`]) and
+/// resources (via [`SaveConfig::resources`]).
+#[derive(Debug, Clone)]
+pub enum ComponentFilter {
+    Allow(HashSet<TypeId>),
+    Deny(HashSet<TypeId>),
+}
+
+impl ComponentFilter {
+    pub fn allow(types: impl IntoIterator<Item = TypeId>) -> Self {
+        Self::Allow(types.into_iter().collect())
+    }
+
+    pub fn deny(types: impl IntoIterator<Item = TypeId>) -> Self {
+        Self::Deny(types.into_iter().collect())
+    }
+
+    fn passes(&self, type_id: TypeId) -> bool {
+        match self {
+            ComponentFilter::Allow(types) => types.contains(&type_id),
+            ComponentFilter::Deny(types) => !types.contains(&type_id),
+        }
+    }
+}
+
+/// Controls what [`save_world`] writes out. Defaults to saving every
+/// reflected, registered component but no resources, since most resources
+/// (asset server state, render handles, ...) aren't meaningful to persist.
+#[derive(Resource, Debug, Clone)]
+pub struct SaveConfig {
+    pub components: ComponentFilter,
+    pub resources: ComponentFilter,
+}
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        Self {
+            components: ComponentFilter::deny(iter::empty()),
+            resources: ComponentFilter::allow(iter::empty()),
+        }
+    }
+}
+
+/// A single reflected value (component or resource), kept as its own RON
+/// string so [`SavedWorld`] doesn't need a custom top-level (de)serializer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedValue {
+    type_path: String,
+    ron: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedEntity {
+    name: String,
+    /// Name of the nearest ancestor that was itself retained; `None` for
+    /// a root or for an entity whose whole ancestor chain got filtered out.
+    parent: Option<String>,
+    object: GameObject,
+    transform: Transform,
+    /// Everything else reflected off the entity (e.g. components a
+    /// blueprint-spawned entity picked up from its glTF), applied by
+    /// [`rehydrate_spawned_entities`] once the entity exists again.
+    components: Vec<SavedValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct SavedWorld {
+    entities: Vec<SavedEntity>,
+    resources: Vec<SavedValue>,
+}
+
+/// Extra reflected components waiting to be re-applied to a rehydrated
+/// entity, keyed by the [`Name`] it will be spawned with. An entry lingers
+/// here until its entity shows up, which may take more than one frame for
+/// blueprint-spawned entities that are still loading their glTF.
+#[derive(Resource, Default)]
+struct PendingRehydration(HashMap<String, Vec<SavedValue>>);
+
+fn save_world(world: &mut World) {
+    let save_requests: Vec<_> = world
+        .resource_mut::<Events<SaveRequest>>()
+        .drain()
+        .collect();
+    if save_requests.is_empty() {
+        return;
+    }
+
+    let save_config = world.resource::<SaveConfig>().clone();
+    let type_registry = world.resource::<AppTypeRegistry>().0.clone();
+    let type_registry = type_registry.read();
+
+    for save in save_requests {
         let scene = save.filename.clone();
         let valid_candidates: Vec<_> = iter::once(scene.clone())
-            .chain((1..).into_iter().map(|n| format!("{0}-{n}", scene.clone())))
+            .chain((1..).map(|n| format!("{0}-{n}", scene.clone())))
             .map(|filename| {
                 Path::new("assets")
                     .join("scenes")
@@ -46,52 +153,88 @@ fn save_world(
             .collect();
         if valid_candidates.is_empty() {
             error!("Failed to save scene \"{}\": Invalid path", scene);
-        } else {
-            if let Some(path) = valid_candidates
-                .iter()
-                .filter_map(|(path, exists)| (!exists).then(|| path))
-                .next()
-            {
-                let serialized_world = serialize_world(&spawn_query);
-                fs::write(path, serialized_world)
-                    .unwrap_or_else(|e| error!("Failed to save scene \"{}\": {}", scene, e));
+            continue;
+        }
+        let Some(path) = valid_candidates
+            .iter()
+            .filter_map(|(path, exists)| (!exists).then_some(path))
+            .next()
+        else {
+            error!(
+                "Failed to save scene \"{}\": Already got too many saves with this name",
+                scene
+            );
+            continue;
+        };
+
+        let serialized_world = serialize_world(world, &type_registry, &save_config);
+        match fs::write(path, serialized_world) {
+            Ok(()) => {
                 info!(
                     "Successfully saved scene \"{}\" at {}",
                     scene,
                     path.to_string_lossy()
                 );
-            } else {
-                error!(
-                    "Failed to save scene \"{}\": Already got too many saves with this name",
-                    scene
-                );
+                world
+                    .resource_mut::<Events<SaveFinished>>()
+                    .send(SaveFinished { path: path.clone() });
             }
+            Err(e) => error!("Failed to save scene \"{}\": {}", scene, e),
         }
     }
 }
 
-fn load_world(
-    mut commands: Commands,
-    mut load_requests: EventReader<LoadRequest>,
-    current_spawn_query: Query<Entity, With<SpawnTracker>>,
-    mut spawn_requests: EventWriter<SpawnEvent>,
-) {
-    for load in load_requests.iter() {
+fn load_world(world: &mut World) {
+    let load_requests: Vec<_> = world
+        .resource_mut::<Events<LoadRequest>>()
+        .drain()
+        .collect();
+    if load_requests.is_empty() {
+        return;
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().0.clone();
+    let type_registry = type_registry.read();
+
+    for load in load_requests {
         let path = Path::new("assets")
             .join("scenes")
             .join(format!("{}.scn.ron", load.filename));
         match fs::read_to_string(&path) {
             Ok(serialized_world) => {
-                let spawn_events = deserialize_world(&serialized_world);
-                for entity in &current_spawn_query {
-                    commands
-                        .get_entity(entity)
-                        .unwrap_or_else(|| panic!("Failed to get entity while loading"))
-                        .despawn_recursive();
+                let saved_world: SavedWorld = ron::from_str(&serialized_world)
+                    .expect("Failed to deserialize world");
+
+                let current_spawned: Vec<Entity> = world
+                    .query_filtered::<Entity, (With<SpawnTracker>, Without<Persistent>, Without<Player>)>()
+                    .iter(world)
+                    .collect();
+                for entity in current_spawned {
+                    DespawnRecursive { entity }.write(world);
                 }
+
+                restore_resources(world, &type_registry, &saved_world.resources);
+
+                let mut pending = HashMap::new();
+                let mut spawn_events = Vec::new();
+                for entity in saved_world.entities {
+                    if !entity.components.is_empty() {
+                        pending.insert(entity.name.clone(), entity.components);
+                    }
+                    spawn_events.push(SpawnEvent {
+                        object: entity.object,
+                        transform: entity.transform,
+                        name: Some(entity.name),
+                        parent: entity.parent,
+                    });
+                }
+                world.resource_mut::<PendingRehydration>().0 = pending;
+
+                let mut spawn_requests = world.resource_mut::<Events<SpawnEvent>>();
                 for event in spawn_events {
                     spawn_requests.send(event);
                 }
+
                 info!(
                     "Successfully loaded scene \"{}\" from {}",
                     load.filename,
@@ -103,31 +246,201 @@ fn load_world(
     }
 }
 
-fn serialize_world(
-    spawn_query: &Query<(&SpawnTracker, &Name, Option<&Parent>, Option<&Transform>)>,
-) -> String {
-    let objects: Vec<_> = spawn_query
-        .iter()
-        .map(|(spawn_tracker, name, parent, transform)| {
-            let parent = parent
-                .map(|parent| spawn_query.get(parent.get()).ok())
-                .flatten()
-                .map(|(spawn_tracker, name, _, _)| {
-                    (spawn_tracker.get_default_name() != name.as_str())
-                        .then(|| name.to_string().into())
-                })
-                .flatten();
-            SpawnEvent {
-                object: spawn_tracker.object,
-                transform: transform.map(Clone::clone).unwrap_or_default(),
-                name: Some(String::from(name).into()),
-                parent,
+/// Applies any [`PendingRehydration`] entries whose named entity now
+/// exists. Entities spawned synchronously by [`crate::spawning::spawn_requested`]
+/// are rehydrated the same frame; blueprint-spawned entities are retried
+/// on subsequent frames until their glTF finishes loading.
+fn rehydrate_spawned_entities(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<PendingRehydration>().0);
+    if pending.is_empty() {
+        return;
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().0.clone();
+    let type_registry = type_registry.read();
+    let mut still_pending = HashMap::new();
+
+    for (name, components) in pending {
+        match find_entity_by_name(world, &name) {
+            Some(entity) => {
+                for component in &components {
+                    let Ok(value) = deserialize_reflected(&component.ron, &type_registry) else {
+                        continue;
+                    };
+                    if let Some(reflect_component) = type_registry
+                        .get_with_name(&component.type_path)
+                        .and_then(|registration| registration.data::<ReflectComponent>())
+                    {
+                        reflect_component.insert(world, entity, &*value);
+                    }
+                }
+            }
+            None => {
+                still_pending.insert(name, components);
             }
+        }
+    }
+
+    world.resource_mut::<PendingRehydration>().0 = still_pending;
+}
+
+fn find_entity_by_name(world: &mut World, name: &str) -> Option<Entity> {
+    world
+        .query::<(Entity, &Name)>()
+        .iter(world)
+        .find(|(_, entity_name)| entity_name.as_str() == name)
+        .map(|(entity, _)| entity)
+}
+
+fn is_retained(world: &World, save_config: &SaveConfig, entity: Entity) -> bool {
+    save_config.components.passes(TypeId::of::<Name>())
+        && world.get::<Name>(entity).is_some()
+        && world.get::<SpawnTracker>(entity).is_some()
+}
+
+/// Types serialized through a dedicated [`SavedEntity`] field rather than
+/// the generic `components` list, so they're excluded from it.
+fn handled_by_spawn_event(type_id: TypeId) -> bool {
+    type_id == TypeId::of::<Name>()
+        || type_id == TypeId::of::<Transform>()
+        || type_id == TypeId::of::<Parent>()
+        || type_id == TypeId::of::<Children>()
+        || type_id == TypeId::of::<SpawnTracker>()
+}
+
+fn reflect_entity_components(
+    world: &World,
+    type_registry: &TypeRegistry,
+    save_config: &SaveConfig,
+    entity: Entity,
+) -> Vec<SavedValue> {
+    let entity_ref = world.entity(entity);
+    entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            if handled_by_spawn_event(type_id) || !save_config.components.passes(type_id) {
+                return None;
+            }
+            let registration = type_registry.get(type_id)?;
+            let reflect_component = registration.data::<ReflectComponent>()?;
+            let value = reflect_component.reflect(entity_ref)?;
+            Some(SavedValue {
+                type_path: registration.type_name().to_string(),
+                ron: ron::to_string(&ReflectSerializer::new(value, type_registry)).ok()?,
+            })
         })
-        .collect();
-    ron::to_string(&objects).expect("Failed to serialize world")
+        .collect()
 }
 
-fn deserialize_world(serialized_world: &str) -> Vec<SpawnEvent> {
-    ron::from_str(serialized_world).expect("Failed to deserialize world")
-}
\ No newline at end of file
+/// Walks the hierarchy rooted at `entity`, appending every retained
+/// descendant to `out`. A filtered-out (non-retained) entity contributes
+/// no [`SavedEntity`] of its own, but its retained descendants are
+/// reparented onto `nearest_retained_ancestor` so the saved file never
+/// references an entity that wasn't written out.
+fn collect_saved_entities(
+    world: &World,
+    type_registry: &TypeRegistry,
+    save_config: &SaveConfig,
+    entity: Entity,
+    nearest_retained_ancestor: Option<String>,
+    out: &mut Vec<SavedEntity>,
+) {
+    let retained = is_retained(world, save_config, entity);
+    let name = world.get::<Name>(entity).map(|n| n.to_string());
+    let parent_for_children = if retained {
+        name.clone()
+    } else {
+        nearest_retained_ancestor.clone()
+    };
+
+    if retained {
+        let object = world
+            .get::<SpawnTracker>(entity)
+            .map(|tracker| tracker.object.clone())
+            .unwrap_or_default();
+        let transform = world.get::<Transform>(entity).copied().unwrap_or_default();
+        out.push(SavedEntity {
+            name: name.expect("is_retained guarantees a Name"),
+            parent: nearest_retained_ancestor,
+            object,
+            transform,
+            components: reflect_entity_components(world, type_registry, save_config, entity),
+        });
+    }
+
+    if let Some(children) = world.get::<Children>(entity) {
+        for &child in children.iter() {
+            collect_saved_entities(
+                world,
+                type_registry,
+                save_config,
+                child,
+                parent_for_children.clone(),
+                out,
+            );
+        }
+    }
+}
+
+fn collect_saved_resources(
+    world: &World,
+    type_registry: &TypeRegistry,
+    save_config: &SaveConfig,
+) -> Vec<SavedValue> {
+    type_registry
+        .iter()
+        .filter(|registration| save_config.resources.passes(registration.type_id()))
+        .filter_map(|registration| {
+            let reflect_resource = registration.data::<ReflectResource>()?;
+            let value = reflect_resource.reflect(world)?;
+            Some(SavedValue {
+                type_path: registration.type_name().to_string(),
+                ron: ron::to_string(&ReflectSerializer::new(value, type_registry)).ok()?,
+            })
+        })
+        .collect()
+}
+
+fn restore_resources(world: &mut World, type_registry: &TypeRegistry, resources: &[SavedValue]) {
+    for saved in resources {
+        let Some(registration) = type_registry.get_with_name(&saved.type_path) else {
+            warn!("Unknown resource type \"{}\" in save file", saved.type_path);
+            continue;
+        };
+        let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+            continue;
+        };
+        match deserialize_reflected(&saved.ron, type_registry) {
+            Ok(value) => reflect_resource.insert(world, &*value),
+            Err(e) => error!("Failed to restore resource \"{}\": {}", saved.type_path, e),
+        }
+    }
+}
+
+fn deserialize_reflected(
+    ron: &str,
+    type_registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, String> {
+    let mut deserializer =
+        ron::Deserializer::from_str(ron).map_err(|e| e.to_string())?;
+    UntypedReflectDeserializer::new(type_registry)
+        .deserialize(&mut deserializer)
+        .map_err(|e| e.to_string())
+}
+
+fn serialize_world(world: &World, type_registry: &TypeRegistry, save_config: &SaveConfig) -> String {
+    let roots: Vec<Entity> = world
+        .query_filtered::<Entity, Without<Parent>>()
+        .iter(world)
+        .collect();
+
+    let mut entities = Vec::new();
+    for root in roots {
+        collect_saved_entities(world, type_registry, save_config, root, None, &mut entities);
+    }
+    let resources = collect_saved_resources(world, type_registry, save_config);
+
+    ron::to_string(&SavedWorld { entities, resources }).expect("Failed to serialize world")
+}